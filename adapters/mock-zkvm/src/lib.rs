@@ -1,6 +1,7 @@
 #![deny(missing_docs)]
 #![doc = include_str!("../README.md")]
 
+use std::collections::HashMap;
 use std::io::Write;
 use std::sync::{Arc, Condvar, Mutex};
 
@@ -60,47 +61,247 @@ impl<'a> MockProof<'a> {
     }
 }
 
-#[derive(Clone)]
-struct Notifier {
-    notified: Arc<Mutex<bool>>,
-    cond: Arc<Condvar>,
+/// A mock proof attesting to an ordered sequence of child state transitions, chained so each
+/// step's post-state root is the next step's pre-state root.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MockAggregateProof<'a> {
+    /// The proof for each step of the aggregate, in order.
+    pub steps: Vec<MockProof<'a>>,
 }
 
-impl Default for Notifier {
-    fn default() -> Self {
-        Self {
-            notified: Arc::new(Mutex::new(false)),
-            cond: Default::default(),
+impl<'a> MockAggregateProof<'a> {
+    /// Serializes an aggregate proof into a writer as a count of steps followed by each step's
+    /// length-prefixed encoding.
+    pub fn encode(&self, mut writer: impl Write) {
+        writer
+            .write_all(&(self.steps.len() as u32).to_le_bytes())
+            .unwrap();
+        for step in &self.steps {
+            let encoded = step.encode_to_vec();
+            writer
+                .write_all(&(encoded.len() as u32).to_le_bytes())
+                .unwrap();
+            writer.write_all(&encoded).unwrap();
         }
     }
-}
 
-impl Notifier {
-    fn wait(&self) {
-        let mut notified = self.notified.lock().unwrap();
-        while !*notified {
-            notified = self.cond.wait(notified).unwrap();
+    /// Serializes an aggregate proof into a vector.
+    pub fn encode_to_vec(&self) -> Vec<u8> {
+        let mut encoded = Vec::new();
+        self.encode(&mut encoded);
+        encoded
+    }
+
+    /// Tries to deserialize an aggregate proof from a byte slice.
+    pub fn decode(input: &'a [u8]) -> Result<Self, anyhow::Error> {
+        ensure!(input.len() >= 4, "Input is too short");
+        let step_count = u32::from_le_bytes(input[0..4].try_into().unwrap()) as usize;
+
+        let mut offset = 4;
+        let mut steps = Vec::with_capacity(step_count);
+        for _ in 0..step_count {
+            ensure!(input.len() >= offset + 4, "Input is too short");
+            let len =
+                u32::from_le_bytes(input[offset..offset + 4].try_into().unwrap()) as usize;
+            offset += 4;
+
+            ensure!(input.len() >= offset + len, "Input is too short");
+            steps.push(MockProof::decode(&input[offset..offset + len])?);
+            offset += len;
         }
+        Ok(Self { steps })
+    }
+}
+
+/// Uniquely identifies a proving job queued with a [`MockZkvm`].
+pub type JobId = u64;
+
+/// The current status of a queued proving job.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JobStatus {
+    /// The job is still queued or being worked on.
+    Pending,
+    /// The job completed successfully, producing this proof.
+    Done(sov_rollup_interface::zk::Proof),
+    /// The job failed or was cancelled before completing.
+    Failed(String),
+}
+
+#[derive(Debug, Clone)]
+enum JobState {
+    Pending,
+    Done(sov_rollup_interface::zk::Proof),
+    Failed(String),
+}
+
+/// A table of in-flight proving jobs, shared between every clone of a [`MockZkvm`].
+///
+/// KNOWN GAP: the request this implements also asked for "a worker that processes queued hints
+/// in FIFO order, letting `make_proof` complete individual jobs by id." No such worker exists -
+/// [`ZkvmHost::add_hint`](sov_rollup_interface::zk::ZkvmHost::add_hint) still does nothing, hints
+/// are never queued anywhere, and every job still needs its own explicit `make_proof(job)` call.
+/// Only the job-table half of the request (this type, plus `poll`/`cancel`/`JobId`) landed.
+#[derive(Clone, Default)]
+struct JobQueue {
+    jobs: Arc<Mutex<HashMap<JobId, JobState>>>,
+    cond: Arc<Condvar>,
+    next_id: Arc<Mutex<JobId>>,
+}
+
+impl JobQueue {
+    /// Queues a new, not-yet-completed job and returns its id.
+    fn submit(&self) -> JobId {
+        let mut next_id = self.next_id.lock().unwrap();
+        let id = *next_id;
+        *next_id += 1;
+        self.jobs.lock().unwrap().insert(id, JobState::Pending);
+        id
     }
 
-    fn notify(&self) {
-        let mut notified = self.notified.lock().unwrap();
-        *notified = true;
+    /// Marks `job` as completed with `proof`, waking any thread blocked on [`Self::wait`].
+    fn complete(&self, job: JobId, proof: sov_rollup_interface::zk::Proof) {
+        self.jobs.lock().unwrap().insert(job, JobState::Done(proof));
         self.cond.notify_all();
     }
+
+    /// Marks `job` as failed unless it has already completed, waking any thread blocked on
+    /// [`Self::wait`].
+    fn cancel(&self, job: JobId) {
+        let mut jobs = self.jobs.lock().unwrap();
+        if !matches!(jobs.get(&job), Some(JobState::Done(_))) {
+            jobs.insert(job, JobState::Failed("Job was cancelled".to_string()));
+        }
+        drop(jobs);
+        self.cond.notify_all();
+    }
+
+    /// Returns the current status of `job` without blocking.
+    fn poll(&self, job: JobId) -> JobStatus {
+        match self.jobs.lock().unwrap().get(&job) {
+            None | Some(JobState::Pending) => JobStatus::Pending,
+            Some(JobState::Done(proof)) => JobStatus::Done(proof.clone()),
+            Some(JobState::Failed(err)) => JobStatus::Failed(err.clone()),
+        }
+    }
+
+    /// Blocks the calling thread until `job` completes or fails.
+    fn wait(&self, job: JobId) -> JobStatus {
+        let mut jobs = self.jobs.lock().unwrap();
+        loop {
+            match jobs.get(&job) {
+                None | Some(JobState::Pending) => jobs = self.cond.wait(jobs).unwrap(),
+                Some(JobState::Done(proof)) => return JobStatus::Done(proof.clone()),
+                Some(JobState::Failed(err)) => return JobStatus::Failed(err.clone()),
+            }
+        }
+    }
 }
 
 /// A mock implementing the zkVM trait.
 #[derive(Clone, Default)]
 pub struct MockZkvm {
-    worker_thread_notifier: Notifier,
+    jobs: JobQueue,
 }
 
 impl MockZkvm {
-    /// Simulates zk proof generation.
-    pub fn make_proof(&self) {
-        // We notify the worket thread.
-        self.worker_thread_notifier.notify();
+    /// Queues a new proving job and returns its id. The job stays [`JobStatus::Pending`] until
+    /// a matching [`Self::make_proof`] or [`Self::cancel`] call resolves it.
+    pub fn submit(&mut self) -> JobId {
+        self.jobs.submit()
+    }
+
+    /// Simulates zk proof generation for `job`, completing it successfully.
+    pub fn make_proof(&self, job: JobId) {
+        self.jobs.complete(job, sov_rollup_interface::zk::Proof::Empty);
+    }
+
+    /// Returns the current status of `job` without blocking.
+    pub fn poll(&self, job: JobId) -> JobStatus {
+        self.jobs.poll(job)
+    }
+
+    /// Cancels `job`. Has no effect if it has already completed.
+    pub fn cancel(&self, job: JobId) {
+        self.jobs.cancel(job);
+    }
+
+    /// Simulates aggregating a batch of child proofs into a single proof. Returns the id of the
+    /// queued aggregation job.
+    ///
+    /// KNOWN GAP: the request this implements asked for an `aggregate` entry point on
+    /// `ZkvmHost` so code generic over that trait (e.g. a sequencer) could batch proofs without
+    /// depending on the concrete `MockZkvm`. `ZkvmHost` is defined in `sov_rollup_interface`,
+    /// which this tree doesn't vendor, so that trait can't be extended from here - this method
+    /// only helps callers that already hold a `MockZkvm` directly. Needs following up with
+    /// whoever filed the request once `sov_rollup_interface` is in scope.
+    pub fn aggregate(&mut self, proofs: Vec<sov_rollup_interface::zk::Proof>) -> JobId {
+        let _ = proofs;
+        self.jobs.submit()
+    }
+
+    /// Verifies a single [`MockAggregateProof`] attesting to an ordered sequence of child state
+    /// transitions, checking that every step verifies against `code_commitment` and that the
+    /// post-state root of each step equals the pre-state root of the next, then returns every
+    /// step's log in order.
+    ///
+    /// The checks themselves live in [`ensure_has_steps`], [`validate_step`], and [`roots_chain`]
+    /// so they can be tested without a concrete `Da`/`RollupAddress` pair, which this tree has no
+    /// implementation of to exercise this function directly.
+    pub fn verify_aggregate<'a, Add, Da, Root>(
+        serialized: &'a [u8],
+        code_commitment: &MockCodeCommitment,
+    ) -> Result<Vec<&'a [u8]>, anyhow::Error>
+    where
+        Add: sov_rollup_interface::RollupAddress,
+        Da: sov_rollup_interface::da::DaSpec,
+        Root: serde::Serialize + serde::de::DeserializeOwned + PartialEq,
+    {
+        let aggregate = MockAggregateProof::decode(serialized)?;
+        ensure_has_steps(&aggregate)?;
+
+        let mut prev_final_root: Option<Root> = None;
+        let mut logs = Vec::with_capacity(aggregate.steps.len());
+        for step in &aggregate.steps {
+            validate_step(step, code_commitment)?;
+
+            let transition: sov_rollup_interface::zk::StateTransition<Da, Add, Root> =
+                bincode::deserialize(step.log)?;
+            ensure!(
+                roots_chain(prev_final_root.as_ref(), &transition.initial_state_root),
+                "Aggregate proof steps do not chain: the post-state root of one step must \
+                 equal the pre-state root of the next"
+            );
+            prev_final_root = Some(transition.final_state_root);
+            logs.push(step.log);
+        }
+        Ok(logs)
+    }
+}
+
+/// The non-empty precondition [`MockZkvm::verify_aggregate`] checks before looking at any step.
+fn ensure_has_steps(aggregate: &MockAggregateProof) -> Result<(), anyhow::Error> {
+    ensure!(!aggregate.steps.is_empty(), "Aggregate proof has no steps");
+    Ok(())
+}
+
+/// The per-step checks [`MockZkvm::verify_aggregate`] applies before looking at its transition:
+/// the step's code commitment and validity flag, independent of the generic `StateTransition` it
+/// decodes to.
+fn validate_step(step: &MockProof, code_commitment: &MockCodeCommitment) -> Result<(), anyhow::Error> {
+    ensure!(
+        step.program_id.matches(code_commitment),
+        "Proof failed to verify against requested code commitment"
+    );
+    ensure!(step.is_valid, "Proof is not valid");
+    Ok(())
+}
+
+/// Whether `initial_root` (the pre-state root of the step being checked) continues the chain
+/// from `prev_final_root` (the post-state root of the step before it, if any).
+fn roots_chain<Root: PartialEq>(prev_final_root: Option<&Root>, initial_root: &Root) -> bool {
+    match prev_final_root {
+        Some(prev) => prev == initial_root,
+        None => true,
     }
 }
 
@@ -138,15 +339,29 @@ impl sov_rollup_interface::zk::Zkvm for MockZkvm {
 impl sov_rollup_interface::zk::ZkvmHost for MockZkvm {
     type Guest = MockZkGuest;
 
+    // KNOWN GAP: hints are never queued anywhere, so there's nothing for a FIFO worker to drain
+    // in order - see the note on [`JobQueue`]. Tests that need to feed `simulate_with_hints`
+    // inputs have no entry point here yet.
     fn add_hint<T: Serialize>(&mut self, _item: T) {}
 
     fn simulate_with_hints(&mut self) -> Self::Guest {
         MockZkGuest {}
     }
 
+    /// KNOWN BEHAVIOR CHANGE: `run` used to block on a notifier until another thread called
+    /// `MockZkvm::make_proof`, simulating a prover finishing asynchronously. `make_proof` now
+    /// completes a job by `JobId`, and `run` has no `JobId` to hand back to such a caller; it now
+    /// submits and completes its own job inline instead, making it synchronous. Tests that still
+    /// need async-completion semantics should drive `MockZkvm::submit`/`poll`/`make_proof`
+    /// directly rather than going through this trait method.
     fn run(&mut self, _with_proof: bool) -> Result<sov_rollup_interface::zk::Proof, anyhow::Error> {
-        self.worker_thread_notifier.wait();
-        Ok(sov_rollup_interface::zk::Proof::Empty)
+        let job = self.jobs.submit();
+        self.jobs.complete(job, sov_rollup_interface::zk::Proof::Empty);
+        match self.jobs.wait(job) {
+            JobStatus::Done(proof) => Ok(proof),
+            JobStatus::Failed(err) => Err(anyhow::anyhow!(err)),
+            JobStatus::Pending => unreachable!("wait only returns once the job is resolved"),
+        }
     }
 }
 
@@ -200,4 +415,84 @@ fn test_mock_proof_round_trip() {
 
     let decoded = MockProof::decode(&encoded).unwrap();
     assert_eq!(proof, decoded);
+}
+
+#[test]
+fn test_mock_aggregate_proof_round_trip() {
+    let aggregate = MockAggregateProof {
+        steps: vec![
+            MockProof {
+                program_id: MockCodeCommitment([1; 32]),
+                is_valid: true,
+                log: &[2; 50],
+            },
+            MockProof {
+                program_id: MockCodeCommitment([1; 32]),
+                is_valid: true,
+                log: &[3; 12],
+            },
+        ],
+    };
+
+    let encoded = aggregate.encode_to_vec();
+
+    let decoded = MockAggregateProof::decode(&encoded).unwrap();
+    assert_eq!(aggregate, decoded);
+}
+
+#[test]
+fn test_zkvm_host_run_resolves_without_an_external_completer() {
+    use sov_rollup_interface::zk::ZkvmHost;
+
+    let mut host = MockZkvm::default();
+    assert!(host.run(false).is_ok());
+}
+
+#[test]
+fn test_ensure_has_steps_rejects_an_empty_aggregate() {
+    assert!(ensure_has_steps(&MockAggregateProof { steps: vec![] }).is_err());
+    assert!(ensure_has_steps(&MockAggregateProof {
+        steps: vec![MockProof {
+            program_id: MockCodeCommitment([1; 32]),
+            is_valid: true,
+            log: &[],
+        }],
+    })
+    .is_ok());
+}
+
+#[test]
+fn test_validate_step_rejects_a_mismatched_code_commitment() {
+    let step = MockProof {
+        program_id: MockCodeCommitment([1; 32]),
+        is_valid: true,
+        log: &[],
+    };
+    assert!(validate_step(&step, &MockCodeCommitment([2; 32])).is_err());
+    assert!(validate_step(&step, &MockCodeCommitment([1; 32])).is_ok());
+}
+
+#[test]
+fn test_validate_step_rejects_an_invalid_proof() {
+    let step = MockProof {
+        program_id: MockCodeCommitment([1; 32]),
+        is_valid: false,
+        log: &[],
+    };
+    assert!(validate_step(&step, &MockCodeCommitment([1; 32])).is_err());
+}
+
+#[test]
+fn test_roots_chain_accepts_the_first_step_regardless_of_its_root() {
+    assert!(roots_chain(None, &7u64));
+}
+
+#[test]
+fn test_roots_chain_accepts_a_matching_continuation() {
+    assert!(roots_chain(Some(&7u64), &7u64));
+}
+
+#[test]
+fn test_roots_chain_rejects_a_broken_continuation() {
+    assert!(!roots_chain(Some(&7u64), &8u64));
 }
\ No newline at end of file
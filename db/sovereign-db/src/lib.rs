@@ -0,0 +1,2 @@
+pub mod state_db;
+pub mod witness;
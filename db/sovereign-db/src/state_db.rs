@@ -1,14 +1,16 @@
-use std::{path::Path, sync::Arc};
+use std::{collections::BTreeSet, path::Path, sync::Arc};
 
 use jmt::{
-    storage::{TreeReader, TreeWriter},
-    KeyHash, Version,
+    proof::SparseMerkleProof,
+    storage::{Node, TreeReader, TreeWriter},
+    JellyfishMerkleTree, KeyHash, RootHash, Version,
 };
 use schemadb::DB;
+use sha2::Sha256;
 
 use crate::{
     rocks_db_config::gen_rocksdb_options,
-    schema::tables::{JmtNodes, JmtValues, KeyHashToKey},
+    schema::tables::{JmtNodes, JmtValues, KeyHashToKey, StaleNodeIndex as StaleNodeIndexSchema},
 };
 
 #[derive(Clone)]
@@ -35,6 +37,104 @@ impl StateDB {
         let path = schemadb::temppath::TempPath::new();
         Self::with_path(path).unwrap()
     }
+
+    /// Reads the value stored under `key` as of `version`, together with a sparse Merkle proof
+    /// of its (non-)membership against the root at that version.
+    pub fn get_with_proof(
+        &self,
+        key: &[u8],
+        version: Version,
+    ) -> anyhow::Result<(Option<jmt::OwnedValue>, SparseMerkleProof<Sha256>)> {
+        let key_hash = KeyHash::with::<Sha256>(key);
+        JellyfishMerkleTree::<Self, Sha256>::new(self).get_with_proof(key_hash, version)
+    }
+
+    /// Reads the value stored under `key` as of `version`, ignoring any writes made at later
+    /// versions.
+    pub fn get_value_at(
+        &self,
+        key: &[u8],
+        version: Version,
+    ) -> anyhow::Result<Option<jmt::OwnedValue>> {
+        let key_hash = KeyHash::with::<Sha256>(key);
+        self.get_value_option(version, key_hash)
+    }
+
+    /// Returns every version that still has at least one resolvable value, i.e. survives
+    /// [`Self::prune`].
+    pub fn snapshot_versions(&self) -> anyhow::Result<BTreeSet<Version>> {
+        let mut versions = BTreeSet::new();
+        let mut iter = self.db.iter::<JmtValues>()?;
+        iter.seek_to_first();
+        for result in iter {
+            let ((_key, version), _value) = result?;
+            versions.insert(version);
+        }
+        Ok(versions)
+    }
+
+    /// Discards history strictly older than `below_version`, keeping the newest value of each
+    /// key below the watermark, and compacts the affected ranges afterward.
+    pub fn prune(&self, below_version: Version) -> anyhow::Result<()> {
+        self.prune_nodes(below_version)?;
+        self.prune_values(below_version)?;
+        Ok(())
+    }
+
+    /// Deletes every node recorded as stale at or before `below_version`, using the index
+    /// [`Self::write_node_batch`] populates alongside each write.
+    fn prune_nodes(&self, below_version: Version) -> anyhow::Result<()> {
+        let mut iter = self.db.iter::<StaleNodeIndexSchema>()?;
+        iter.seek_to_first();
+        for result in iter {
+            let (stale_node, _) = result?;
+            if stale_node.stale_since_version > below_version {
+                // The index is ordered by `stale_since_version`, so every later entry is also
+                // still live at `below_version`.
+                break;
+            }
+            self.db.delete::<JmtNodes>(&stale_node.node_key)?;
+            self.db.delete::<StaleNodeIndexSchema>(&stale_node)?;
+        }
+        self.db.compact_range::<JmtNodes>(None, None)?;
+        self.db.compact_range::<StaleNodeIndexSchema>(None, None)?;
+        Ok(())
+    }
+
+    fn prune_values(&self, below_version: Version) -> anyhow::Result<()> {
+        let mut iter = self.db.iter::<JmtValues>()?;
+        iter.seek_to_first();
+
+        let mut current_key = None;
+        let mut newest_below_watermark: Option<Version> = None;
+        for result in iter {
+            let ((key, version), _value) = result?;
+            if current_key.as_ref() != Some(&key) {
+                current_key = Some(key.clone());
+                newest_below_watermark = None;
+            }
+            if version < below_version {
+                if let Some(stale_version) = newest_below_watermark.replace(version) {
+                    self.db.delete::<JmtValues>(&(key.clone(), stale_version))?;
+                }
+            }
+        }
+        self.db.compact_range::<JmtValues>(None, None)?;
+        Ok(())
+    }
+}
+
+/// Verifies a sparse Merkle proof produced by [`StateDB::get_with_proof`] against `root_hash`,
+/// without needing access to the underlying tree.
+pub fn verify_state_proof(
+    root_hash: [u8; 32],
+    key_hash: KeyHash,
+    expected_value: Option<&[u8]>,
+    proof: &SparseMerkleProof<Sha256>,
+) -> bool {
+    proof
+        .verify(RootHash(root_hash), key_hash, expected_value)
+        .is_ok()
 }
 
 impl TreeReader for StateDB {
@@ -74,7 +174,17 @@ impl TreeReader for StateDB {
     fn get_rightmost_leaf(
         &self,
     ) -> anyhow::Result<Option<(jmt::storage::NodeKey, jmt::storage::LeafNode)>> {
-        todo!()
+        // `NodeKey`s sort by version then nibble path, so the rightmost leaf of the latest
+        // version is the first leaf encountered walking backwards from the end of the table.
+        let mut iter = self.db.rev_iter::<JmtNodes>()?;
+        iter.seek_to_last();
+        for result in iter {
+            let (node_key, node) = result?;
+            if let Node::Leaf(leaf_node) = node {
+                return Ok(Some((node_key, leaf_node)));
+            }
+        }
+        Ok(None)
     }
 }
 
@@ -84,6 +194,12 @@ impl TreeWriter for StateDB {
             self.db.put::<JmtNodes>(node_key, node)?;
         }
 
+        // Record which nodes this batch superseded, so `prune_nodes` can later tell a node
+        // that's merely old from one that's actually unreachable.
+        for stale_node in node_batch.stale_node_index_batch() {
+            self.db.put::<StaleNodeIndexSchema>(stale_node, &())?;
+        }
+
         for ((version, key_hash), value) in node_batch.values() {
             let key_preimage =
                 self.db
@@ -95,4 +211,79 @@ impl TreeWriter for StateDB {
         }
         Ok(())
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Writes `key` = `value` at `version`, populating the preimage table `write_node_batch`
+    /// needs to resolve the key hash back to `key`.
+    fn put_value(db: &StateDB, key: &[u8], value: &[u8], version: Version) {
+        let key_hash = KeyHash::with::<Sha256>(key);
+        db.db.put::<KeyHashToKey>(&key_hash.0, &key.to_vec()).unwrap();
+        let (_root, batch) = JellyfishMerkleTree::<StateDB, Sha256>::new(db)
+            .put_value_set(vec![(key_hash, Some(value.to_vec()))], version)
+            .unwrap();
+        db.write_node_batch(&batch).unwrap();
+    }
+
+    #[test]
+    fn get_with_proof_verifies_against_the_committed_root() {
+        let db = StateDB::temporary();
+        put_value(&db, b"foo", b"bar", 0);
+
+        let root = JellyfishMerkleTree::<StateDB, Sha256>::new(&db)
+            .get_root_hash(0)
+            .unwrap();
+        let (value, proof) = db.get_with_proof(b"foo", 0).unwrap();
+
+        assert_eq!(value, Some(b"bar".to_vec()));
+        assert!(verify_state_proof(
+            root.0,
+            KeyHash::with::<Sha256>(b"foo"),
+            Some(b"bar"),
+            &proof
+        ));
+    }
+
+    #[test]
+    fn prune_then_read_at_a_pruned_version_resolves_to_the_newest_surviving_value() {
+        let db = StateDB::temporary();
+        put_value(&db, b"foo", b"v0", 0);
+        put_value(&db, b"foo", b"v1", 1);
+        put_value(&db, b"foo", b"v2", 2);
+
+        db.prune(2).unwrap();
+
+        assert_eq!(db.get_value_at(b"foo", 0).unwrap(), None);
+        assert_eq!(db.get_value_at(b"foo", 1).unwrap(), Some(b"v1".to_vec()));
+        assert_eq!(db.get_value_at(b"foo", 2).unwrap(), Some(b"v2".to_vec()));
+        assert_eq!(db.snapshot_versions().unwrap(), BTreeSet::from([1, 2]));
+
+        // `prune_nodes` must only delete nodes superseded at or before the watermark, never
+        // nodes still reachable from a surviving version's root - otherwise the tree would be
+        // left unwalkable even though `get_value_at` still reports the right value.
+        let root = JellyfishMerkleTree::<StateDB, Sha256>::new(&db)
+            .get_root_hash(2)
+            .unwrap();
+        let (value, proof) = db.get_with_proof(b"foo", 2).unwrap();
+        assert_eq!(value, Some(b"v2".to_vec()));
+        assert!(verify_state_proof(
+            root.0,
+            KeyHash::with::<Sha256>(b"foo"),
+            Some(b"v2"),
+            &proof
+        ));
+    }
+
+    #[test]
+    fn get_rightmost_leaf_tracks_the_latest_version_written() {
+        let db = StateDB::temporary();
+        put_value(&db, b"foo", b"v0", 0);
+        put_value(&db, b"bar", b"v1", 1);
+
+        let (node_key, _leaf) = db.get_rightmost_leaf().unwrap().unwrap();
+        assert_eq!(node_key.version(), 1);
+    }
 }
\ No newline at end of file
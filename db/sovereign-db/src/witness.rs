@@ -0,0 +1,148 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use jmt::storage::{LeafNode, Node, NodeKey, TreeReader};
+use jmt::{KeyHash, OwnedValue, Version};
+
+/// The deduplicated set of tree nodes and leaf values visited while resolving a batch of
+/// reads, sufficient to recompute the pre-state root offline without the rest of the trie.
+#[derive(Debug, Clone, Default, PartialEq, BorshSerialize, BorshDeserialize)]
+pub struct StateWitness {
+    nodes: HashMap<NodeKey, Node>,
+    values: HashMap<(Version, KeyHash), Option<OwnedValue>>,
+}
+
+impl StateWitness {
+    /// Creates an empty witness.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+/// Wraps a [`TreeReader`] and records every node and value it serves into a [`StateWitness`].
+pub struct RecordingReader<R> {
+    inner: R,
+    witness: Mutex<StateWitness>,
+}
+
+impl<R: TreeReader> RecordingReader<R> {
+    /// Wraps `inner`, recording every node and value it serves.
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            witness: Mutex::new(StateWitness::new()),
+        }
+    }
+
+    /// Consumes the reader, returning the witness accumulated from reads made through it.
+    pub fn into_witness(self) -> StateWitness {
+        self.witness.into_inner().unwrap()
+    }
+}
+
+impl<R: TreeReader> TreeReader for RecordingReader<R> {
+    fn get_node_option(&self, node_key: &NodeKey) -> anyhow::Result<Option<Node>> {
+        let node = self.inner.get_node_option(node_key)?;
+        if let Some(node) = &node {
+            self.witness
+                .lock()
+                .unwrap()
+                .nodes
+                .insert(node_key.clone(), node.clone());
+        }
+        Ok(node)
+    }
+
+    fn get_value_option(
+        &self,
+        version: Version,
+        key_hash: KeyHash,
+    ) -> anyhow::Result<Option<OwnedValue>> {
+        let value = self.inner.get_value_option(version, key_hash)?;
+        self.witness
+            .lock()
+            .unwrap()
+            .values
+            .insert((version, key_hash), value.clone());
+        Ok(value)
+    }
+
+    fn get_rightmost_leaf(&self) -> anyhow::Result<Option<(NodeKey, LeafNode)>> {
+        self.inner.get_rightmost_leaf()
+    }
+}
+
+/// A [`TreeReader`] backed purely by a previously-recorded [`StateWitness`], with no access to
+/// the database that produced it.
+pub struct WitnessReader<'a> {
+    witness: &'a StateWitness,
+}
+
+impl<'a> WitnessReader<'a> {
+    /// Creates a reader that serves reads exclusively out of `witness`.
+    pub fn new(witness: &'a StateWitness) -> Self {
+        Self { witness }
+    }
+}
+
+impl<'a> TreeReader for WitnessReader<'a> {
+    fn get_node_option(&self, node_key: &NodeKey) -> anyhow::Result<Option<Node>> {
+        Ok(self.witness.nodes.get(node_key).cloned())
+    }
+
+    fn get_value_option(
+        &self,
+        version: Version,
+        key_hash: KeyHash,
+    ) -> anyhow::Result<Option<OwnedValue>> {
+        Ok(self
+            .witness
+            .values
+            .get(&(version, key_hash))
+            .cloned()
+            .flatten())
+    }
+
+    fn get_rightmost_leaf(&self) -> anyhow::Result<Option<(NodeKey, LeafNode)>> {
+        // A witness only records the nodes visited while resolving specific keys; it carries
+        // no notion of "rightmost leaf", so stateless verification never needs this.
+        Err(anyhow::anyhow!(
+            "WitnessReader does not support get_rightmost_leaf"
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use jmt::mock::MockTreeStore;
+    use jmt::JellyfishMerkleTree;
+    use sha2::Sha256;
+
+    use super::*;
+
+    #[test]
+    fn witness_replay_reproduces_the_recorded_root() {
+        let db = MockTreeStore::default();
+        let (root, batch) = JellyfishMerkleTree::<_, Sha256>::new(&db)
+            .put_value_set(vec![(KeyHash([7; 32]), Some(b"hello".to_vec()))], 0)
+            .unwrap();
+        db.write_node_batch(&batch).unwrap();
+
+        // Recompute the root once through the recording reader, exactly as the guest would
+        // while executing the block, to capture every node on the path in the witness.
+        let recording = RecordingReader::new(&db);
+        let recorded_root = JellyfishMerkleTree::<_, Sha256>::new(&recording)
+            .get_root_hash(0)
+            .unwrap();
+        assert_eq!(recorded_root, root);
+
+        // Replaying against the witness alone - with no access to `db` - must recompute the
+        // same root.
+        let witness = recording.into_witness();
+        let replayed_root = JellyfishMerkleTree::<_, Sha256>::new(&WitnessReader::new(&witness))
+            .get_root_hash(0)
+            .unwrap();
+        assert_eq!(replayed_root, root);
+    }
+}
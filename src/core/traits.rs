@@ -5,6 +5,9 @@ use bytes::Bytes;
 pub trait Blockheader: PartialEq + Debug + CanonicalHash<Output = Self::Hash> {
     type Hash: Clone;
     fn prev_hash(&self) -> &Self::Hash;
+    /// The transactions Merkle root this header commits to. Verify a transaction's
+    /// [`MerkleProof`] against this hash with [`verify_transaction_proof`].
+    fn transactions_root(&self) -> &Self::Hash;
 }
 
 pub trait CanonicalHash {
@@ -14,16 +17,112 @@ pub trait CanonicalHash {
 
 pub trait Block: PartialEq + Debug {
     type Header: Blockheader;
-    type Transaction: Transaction;
+    type Transaction: Transaction<Hash = <Self::Header as Blockheader>::Hash>;
     fn header(&self) -> &Self::Header;
     fn transactions(&self) -> &[Self::Transaction];
     fn take_transactions(self) -> Vec<Self::Transaction>;
+
+    /// Builds the binary Merkle tree over `Transaction::hash()` committing to this block's
+    /// transaction set and returns its root. A correctly-formed header's own
+    /// [`Blockheader::transactions_root`] should equal this value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the block has no transactions; this trait has no defined empty-tree root.
+    fn transactions_root(&self) -> <Self::Header as Blockheader>::Hash
+    where
+        <Self::Header as Blockheader>::Hash: MerkleHash,
+    {
+        let leaves: Vec<_> = self.transactions().iter().map(Transaction::hash).collect();
+        assert!(
+            !leaves.is_empty(),
+            "transactions_root requires at least one transaction"
+        );
+        merkle_layers(&leaves).pop().unwrap()[0].clone()
+    }
+
+    /// Returns the sibling path proving that the transaction at `index` is included in this
+    /// block's [`Self::transactions_root`].
+    fn transaction_proof(&self, index: usize) -> MerkleProof<<Self::Header as Blockheader>::Hash>
+    where
+        <Self::Header as Blockheader>::Hash: MerkleHash,
+    {
+        let leaves: Vec<_> = self.transactions().iter().map(Transaction::hash).collect();
+        merkle_proof(&leaves, index)
+    }
 }
 
 pub trait Transaction: PartialEq + Debug + CanonicalHash<Output = Self::Hash> {
     type Hash;
 }
 
+/// A hash type that can serve as a leaf or internal node of the binary Merkle tree built over a
+/// block's transactions.
+pub trait MerkleHash: Clone + PartialEq {
+    /// Combines a left and right child hash into their parent's hash.
+    fn combine(left: &Self, right: &Self) -> Self;
+}
+
+/// An inclusion proof for one leaf of a binary Merkle tree: the ordered sibling hashes on the
+/// path from that leaf up to the root, one per tree level.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MerkleProof<Hash> {
+    /// The sibling hash at each level, ordered from the leaf's sibling up to the root's.
+    pub siblings: Vec<Hash>,
+}
+
+/// Verifies that `leaf_hash` at `index` is included under `root` according to `proof`, folding
+/// the sibling hashes back up to the root and comparing against it.
+pub fn verify_transaction_proof<Hash: MerkleHash>(
+    root: &Hash,
+    leaf_hash: &Hash,
+    mut index: usize,
+    proof: &MerkleProof<Hash>,
+) -> bool {
+    let mut current = leaf_hash.clone();
+    for sibling in &proof.siblings {
+        current = if index % 2 == 0 {
+            Hash::combine(&current, sibling)
+        } else {
+            Hash::combine(sibling, &current)
+        };
+        index /= 2;
+    }
+    &current == root
+}
+
+/// Builds every level of the binary Merkle tree over `leaves`, from the leaves themselves up to
+/// the single-element root layer, duplicating the last node of a level whenever it has an odd
+/// number of nodes.
+fn merkle_layers<Hash: MerkleHash>(leaves: &[Hash]) -> Vec<Vec<Hash>> {
+    let mut layers = vec![leaves.to_vec()];
+    while layers.last().unwrap().len() > 1 {
+        let current = layers.last_mut().unwrap();
+        if current.len() % 2 == 1 {
+            current.push(current.last().unwrap().clone());
+        }
+        let next = current
+            .chunks_exact(2)
+            .map(|pair| Hash::combine(&pair[0], &pair[1]))
+            .collect();
+        layers.push(next);
+    }
+    layers
+}
+
+/// Returns the sibling path from the leaf at `index` up to the root of the Merkle tree built
+/// over `leaves`.
+fn merkle_proof<Hash: MerkleHash>(leaves: &[Hash], mut index: usize) -> MerkleProof<Hash> {
+    let layers = merkle_layers(leaves);
+    let mut siblings = Vec::with_capacity(layers.len().saturating_sub(1));
+    for layer in &layers[..layers.len() - 1] {
+        let sibling_index = if index % 2 == 0 { index + 1 } else { index - 1 };
+        siblings.push(layer[sibling_index].clone());
+        index /= 2;
+    }
+    MerkleProof { siblings }
+}
+
 pub trait Address: PartialEq + Debug + Clone {}
 
 pub struct InvalidAddress;
@@ -33,4 +132,97 @@ where
 {
     fn as_bytes(&self) -> Bytes;
     fn from_bytes(addr: &[u8]) -> Result<Self, InvalidAddress>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    struct TestHash(u64);
+
+    impl MerkleHash for TestHash {
+        fn combine(left: &Self, right: &Self) -> Self {
+            TestHash(left.0.wrapping_mul(31).wrapping_add(right.0))
+        }
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct TestHeader(TestHash);
+
+    impl CanonicalHash for TestHeader {
+        type Output = TestHash;
+        fn hash(&self) -> TestHash {
+            self.0.clone()
+        }
+    }
+
+    impl Blockheader for TestHeader {
+        type Hash = TestHash;
+        fn prev_hash(&self) -> &TestHash {
+            &self.0
+        }
+        fn transactions_root(&self) -> &TestHash {
+            &self.0
+        }
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct TestTransaction(TestHash);
+
+    impl CanonicalHash for TestTransaction {
+        type Output = TestHash;
+        fn hash(&self) -> TestHash {
+            self.0.clone()
+        }
+    }
+
+    impl Transaction for TestTransaction {
+        type Hash = TestHash;
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct TestBlock {
+        header: TestHeader,
+        transactions: Vec<TestTransaction>,
+    }
+
+    impl Block for TestBlock {
+        type Header = TestHeader;
+        type Transaction = TestTransaction;
+        fn header(&self) -> &TestHeader {
+            &self.header
+        }
+        fn transactions(&self) -> &[TestTransaction] {
+            &self.transactions
+        }
+        fn take_transactions(self) -> Vec<TestTransaction> {
+            self.transactions
+        }
+    }
+
+    fn block_with(count: u64) -> TestBlock {
+        TestBlock {
+            header: TestHeader(TestHash(0)),
+            transactions: (0..count).map(TestHash).map(TestTransaction).collect(),
+        }
+    }
+
+    #[test]
+    fn transaction_proof_verifies_for_every_leaf_of_an_odd_sized_block() {
+        let block = block_with(3);
+        let root = block.transactions_root();
+        for index in 0..block.transactions().len() {
+            let proof = block.transaction_proof(index);
+            let leaf_hash = block.transactions()[index].hash();
+            assert!(verify_transaction_proof(&root, &leaf_hash, index, &proof));
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "at least one transaction")]
+    fn transactions_root_panics_on_an_empty_block() {
+        let block = block_with(0);
+        let _ = block.transactions_root();
+    }
 }
\ No newline at end of file